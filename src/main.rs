@@ -1,5 +1,3 @@
-use std::f32::consts::PI;
-
 use bevy::{
     input::mouse::MouseMotion,
     math::vec2,
@@ -12,15 +10,52 @@ use bevy::{
     window::CursorGrabMode,
 };
 
+mod pathfinding;
+mod quadtree;
+mod svg_path;
+
+use std::collections::{HashMap, VecDeque};
+
+use pathfinding::PathGraph;
+use quadtree::{Aabb, Quadtree};
+
+// The outline used when no other path is supplied. It traces the same
+// equilateral triangle the demo used to hardcode, so the default outline
+// is unchanged.
+const DEFAULT_POLYGON_PATH: &str =
+    "M 0,200 L -173.2,-100 L 173.2,-100 Z";
+
+// How far (in pixels) the stroke mesh extends outward from the path,
+// matching the old triangle's inner-to-outer radius gap.
+const STROKE_THICKNESS: f32 = 50.;
+
 // Here we create the bevy app and connect items that we need for it to work
 // like we want it to
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
         .init_resource::<Game>()
-        .add_startup_system(create_triangle)
+        .init_resource::<PickingRadius>()
+        .add_event::<SegmentHovered>()
+        .add_event::<SegmentClicked>()
+        // `create_polygon` spawns entities via `Commands`, which aren't
+        // applied until its stage ends. Run it in `PreStartup` so the
+        // graph/index builders below (in the default `Startup` stage)
+        // see the spawned `TriangleSegment`s instead of an empty world;
+        // `.after()` alone only orders execution, it doesn't flush
+        // commands.
+        .add_startup_system_to_stage(
+            StartupStage::PreStartup,
+            create_polygon,
+        )
+        .add_startup_system(build_path_graph)
+        .add_startup_system(build_segment_index)
         .add_startup_system(create_cursor)
         .add_system(move_cursor)
+        .add_system(pick_segments)
+        .add_system(play_hover_sound)
+        .add_system(start_navigation.after(pick_segments))
+        .add_system(navigate_cursor)
         .run();
 }
 
@@ -38,70 +73,54 @@ struct TriangleSegment {
     normal: Vec2,
 }
 
-// This system creates the triangle that is drawn on screen
-fn create_triangle(
+// Stores a segment's un-hovered color so the picking system can restore
+// it once the cursor moves away.
+#[derive(Component)]
+struct SegmentBaseColor(Color);
+
+// This system builds the polygon outline that is drawn on screen and that
+// the cursor slides along. It parses an SVG path's M/L/C/Q/Z commands
+// (flattening curves with the helpers in `svg_path`) into a closed vertex
+// loop, then spawns one `TriangleSegment` per edge, same as the old
+// hardcoded triangle did.
+fn create_polygon(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
-    let mut segments =
-        vec![
-            Mesh::new(PrimitiveTopology::TriangleStrip);
-            3
-        ];
-
-    // Triangle points are positioned as follows
-    //     1
-    //     0
-    //
-    //  4     2
-    // 5       3
-
-    let points: Vec<[f32; 3]> = (0..3)
-        .map(|i| i as f32 * PI / 3. * 2. + (PI / 2.))
-        .map(|i| (i.cos(), i.sin()))
-        .flat_map(|(cos, sin)| {
-            [
-                [cos * 200., sin * 200., 1.],
-                [cos * 250., sin * 250., 1.],
-            ]
-        })
-        .collect();
-
-    for (i, (wind, color)) in [0, 1, 2, 3, 4, 5, 0, 1]
-        .map(|i| points.get(i).unwrap().to_owned())
-        .windows(4)
-        .step_by(2)
-        .zip(vec![
-            ColorMaterial::from(Color::RED),
-            ColorMaterial::from(Color::GREEN),
-            ColorMaterial::from(Color::BLUE),
-        ])
+    let vertices = svg_path::parse_polygon(DEFAULT_POLYGON_PATH);
+    let edge_count = vertices.len();
+
+    for (i, (&start, &end)) in vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
         .enumerate()
     {
-        let segment = segments.get_mut(i).unwrap();
-        segment.insert_attribute(
+        let between = end - start;
+        let normal = Vec2::new(between.y, -between.x);
+        let offset = normal.normalize_or_zero() * STROKE_THICKNESS;
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleStrip);
+        mesh.insert_attribute(
             Mesh::ATTRIBUTE_POSITION,
-            wind.to_vec(),
+            vec![
+                [start.x, start.y, 1.],
+                [start.x + offset.x, start.y + offset.y, 1.],
+                [end.x, end.y, 1.],
+                [end.x + offset.x, end.y + offset.y, 1.],
+            ],
         );
 
-        let start = wind
-            .get(0)
-            .map(|s| Vec2::new(s[0], s[1]))
-            .unwrap();
-        let end = wind
-            .get(2)
-            .map(|s| Vec2::new(s[0], s[1]))
-            .unwrap();
-
-        let between = end - start;
-
-        let normal = Vec2::new(between.y, -between.x);
+        let color = Color::hsl(
+            i as f32 / edge_count as f32 * 360.,
+            0.7,
+            0.5,
+        );
 
         commands.spawn((
             MaterialMesh2dBundle {
-                mesh: meshes.add(segment.to_owned()).into(),
-                material: materials.add(color),
+                mesh: meshes.add(mesh).into(),
+                material: materials.add(ColorMaterial::from(color)),
                 transform: Transform::from_translation(
                     Vec3::new(0., 0., 1.),
                 ),
@@ -114,6 +133,7 @@ fn create_triangle(
                 between,
                 normal,
             },
+            SegmentBaseColor(color),
         ));
     }
 
@@ -128,6 +148,8 @@ enum CursorState {
     Unlocked,
     // sliding on segment id
     Sliding(usize),
+    // walking the remaining vertices of an A* route along the outline
+    Navigating(VecDeque<Vec2>),
 }
 
 #[derive(Resource, Default)]
@@ -197,6 +219,7 @@ fn move_cursor(
         With<GameCursor>,
     >,
     triangle_segments: Query<&TriangleSegment>,
+    segment_index: Res<SegmentIndex>,
     mut cursor_pos: EventReader<MouseMotion>,
     mut game: ResMut<Game>,
     asset_server: Res<AssetServer>,
@@ -209,29 +232,38 @@ fn move_cursor(
         let mut future_pos = cursor + delta;
         match game.cursor_state {
             CursorState::Unlocked => {
-                for TriangleSegment {
-                    id,
-                    start,
-                    end,
-                    between,
-                    ..
-                } in &triangle_segments
-                {
+                let sweep =
+                    Aabb::from_points(cursor, future_pos);
+                let mut candidates = Vec::new();
+                segment_index.0.query(&sweep, &mut candidates);
+
+                for id in candidates {
+                    let Some(TriangleSegment {
+                        start,
+                        end,
+                        between,
+                        ..
+                    }) = triangle_segments
+                        .iter()
+                        .find(|segment| segment.id == id)
+                    else {
+                        continue;
+                    };
+
                     if (future_pos - *start).cross(between)
                         > 0.
                     {
                         // Cursor is past the segment
-                        if let Some(fp) = get_intersection(
+                        if let Some((fp, _t)) = get_intersection(
                             (&cursor, &future_pos),
                             (&start, &end),
                         ) {
-                            println!("{fp} {cursor} {future_pos} {start} {end}");
                             future_pos = fp;
                         } else {
                             break;
                         };
                         game.cursor_state =
-                            CursorState::Sliding(*id);
+                            CursorState::Sliding(id);
                         audio.play(asset_server.load(
                             &format!("sounds/{}.wav", id),
                         ));
@@ -245,7 +277,9 @@ fn move_cursor(
                     end,
                     between,
                     ..
-                }) = triangle_segments.iter().nth(id)
+                }) = triangle_segments
+                    .iter()
+                    .find(|segment| segment.id == id)
                 {
                     if (cursor + delta - *start)
                         .cross(between)
@@ -289,6 +323,9 @@ fn move_cursor(
                     }
                 }
             }
+            // `navigate_cursor` drives the cursor while a route is
+            // active; manual mouse motion is ignored until it's done.
+            CursorState::Navigating(_) => continue,
         }
 
         let trans =
@@ -298,35 +335,314 @@ fn move_cursor(
     }
 }
 
-// FIXME: Sometimes it jumps around not sure if this func is the problem
+// Epsilon used when checking whether t/u fall inside [0, 1], so hits
+// right on a segment's endpoint aren't rejected by float noise.
+const SEGMENT_EPSILON: f32 = 1e-4;
+
+// Parametric segment-segment intersection. Unlike a plain line-line
+// intersection, this only returns a hit when it actually lies on both
+// finite segments P1->P2 and P3->P4, which is what was causing the
+// cursor to jump onto off-segment extensions of the line.
+//
+// Given r = P2-P1 and s = P4-P3, solve P1 + t*r == P3 + u*s for t and u
+// via the 2D cross product and accept the hit only if both are in
+// [0, 1]. Returns the hit point along with `t` so callers can reuse it
+// (e.g. to know how far along the swept motion the crossing happened).
 fn get_intersection(
     (p1, p2): (&Vec2, &Vec2),
     (p3, p4): (&Vec2, &Vec2),
-) -> Option<Vec2> {
-    // y difference of points 1 and 2, 3 and 4
-    // Used multiple times so saved to a var
-    let yd12 = p1.y - p2.y;
-    let yd34 = p3.y - p4.y;
+) -> Option<(Vec2, f32)> {
+    let r = *p2 - *p1;
+    let s = *p4 - *p3;
 
-    let denominator =
-        (p1.x - p2.x) * yd34 - yd12 * (p3.x - p4.x);
+    let denom = r.cross(&s);
 
-    // Parallel or coincident
-    if denominator == 0. {
+    // Parallel or collinear
+    if denom.abs() < f32::EPSILON {
         return None;
     }
 
-    let det12 = p1.x * p2.y - p1.y * p2.x;
-    let det34 = p3.x * p4.y - p3.y * p4.x;
-    println!("{det12} {det34}");
+    let p3_minus_p1 = *p3 - *p1;
+    let t = p3_minus_p1.cross(&s) / denom;
+    let u = p3_minus_p1.cross(&r) / denom;
+
+    let in_range = |v: f32| {
+        v >= -SEGMENT_EPSILON && v <= 1. + SEGMENT_EPSILON
+    };
+
+    if in_range(t) && in_range(u) {
+        Some((*p1 + r * t, t))
+    } else {
+        None
+    }
+}
+
+// How close (in pixels) the cursor has to be to a segment before it
+// counts as hovered.
+#[derive(Resource)]
+struct PickingRadius(f32);
+
+impl Default for PickingRadius {
+    fn default() -> Self {
+        Self(20.)
+    }
+}
+
+// The tint applied to a segment while the cursor is hovering over it.
+const HOVER_TINT: Color = Color::WHITE;
+
+// Fired when the cursor starts hovering a new segment.
+struct SegmentHovered(usize);
+
+// Fired when the segment currently under the cursor is clicked. Picking
+// itself never touches `CursorState` — `start_navigation` (chunk0-4) is
+// the consumer, and it routes a click to `CursorState::Navigating` (an
+// A* walk to the clicked edge) rather than forcing `Sliding` directly.
+struct SegmentClicked(usize);
+
+// Mouse picking backend: each frame, finds the `TriangleSegment` nearest
+// to the cursor (by point-to-finite-segment distance, i.e. projecting
+// `cursor - start` onto `between` and clamping to `[0, |between|]`),
+// tints it while hovered, and emits `SegmentClicked` on click so other
+// systems (e.g. `start_navigation`) can decide what that means.
+fn pick_segments(
+    cursor_component: Query<&Transform, With<GameCursor>>,
+    triangle_segments: Query<(
+        &TriangleSegment,
+        &Handle<ColorMaterial>,
+        &SegmentBaseColor,
+    )>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mouse_button: Res<Input<MouseButton>>,
+    picking_radius: Res<PickingRadius>,
+    mut hovered: Local<Option<usize>>,
+    mut hover_events: EventWriter<SegmentHovered>,
+    mut click_events: EventWriter<SegmentClicked>,
+) {
+    let cursor =
+        cursor_component.single().translation.to_vec2();
+
+    let nearest = triangle_segments
+        .iter()
+        .map(|(segment, material, base_color)| {
+            let dist = point_to_segment_distance(
+                cursor,
+                segment.start,
+                segment.between,
+            );
+            (segment, material, base_color, dist)
+        })
+        .filter(|(.., dist)| *dist <= picking_radius.0)
+        .min_by(|a, b| a.3.total_cmp(&b.3));
+
+    let nearest_id = nearest.as_ref().map(|(segment, ..)| segment.id);
+
+    if *hovered != nearest_id {
+        if let Some(old_id) = *hovered {
+            if let Some((_, material, base_color, _)) =
+                triangle_segments
+                    .iter()
+                    .find(|(segment, ..)| segment.id == old_id)
+            {
+                if let Some(mat) = materials.get_mut(material) {
+                    mat.color = base_color.0;
+                }
+            }
+        }
+
+        if let Some(id) = nearest_id {
+            hover_events.send(SegmentHovered(id));
+        }
+
+        *hovered = nearest_id;
+    }
+
+    if let Some((segment, material, _, _)) = nearest {
+        if let Some(mat) = materials.get_mut(material) {
+            mat.color = HOVER_TINT;
+        }
+
+        if mouse_button.just_pressed(MouseButton::Left) {
+            click_events.send(SegmentClicked(segment.id));
+        }
+    }
+}
+
+// Plays a per-segment audio cue whenever `SegmentHovered` fires, same
+// sound-by-id convention `move_cursor` already uses for the bump-into
+// case.
+fn play_hover_sound(
+    mut hover_events: EventReader<SegmentHovered>,
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+) {
+    for SegmentHovered(id) in hover_events.iter() {
+        audio.play(
+            asset_server.load(&format!("sounds/hover_{id}.wav")),
+        );
+    }
+}
+
+fn point_to_segment_distance(
+    point: Vec2,
+    start: Vec2,
+    between: Vec2,
+) -> f32 {
+    let len_sq = between.length_squared();
+    if len_sq == 0. {
+        return point.distance(start);
+    }
+
+    let t = ((point - start).dot(between) / len_sq)
+        .clamp(0., 1.);
+    point.distance(start + between * t)
+}
+
+// Builds the `PathGraph` navigation uses: one node per polygon vertex,
+// one weighted edge per `TriangleSegment`. `TriangleSegment::id` already
+// indexes the vertex loop (`create_polygon` spawns segment `i` running
+// from vertex `i` to vertex `i + 1`), so the vertex list and adjacency
+// fall out of the segments directly.
+fn build_path_graph(
+    mut commands: Commands,
+    triangle_segments: Query<&TriangleSegment>,
+) {
+    let mut segments: Vec<&TriangleSegment> =
+        triangle_segments.iter().collect();
+    segments.sort_by_key(|segment| segment.id);
+
+    let vertices: Vec<Vec2> =
+        segments.iter().map(|segment| segment.start).collect();
+    let mut adjacency: HashMap<usize, Vec<(usize, f32)>> =
+        HashMap::new();
+
+    for segment in &segments {
+        let from = segment.id;
+        let to = (segment.id + 1) % vertices.len();
+        let weight = segment.start.distance(segment.end);
+
+        adjacency.entry(from).or_default().push((to, weight));
+        adjacency.entry(to).or_default().push((from, weight));
+    }
+
+    commands.insert_resource(PathGraph { vertices, adjacency });
+}
+
+// Reacts to `SegmentClicked`: runs A* from whichever polygon vertex is
+// currently closest to the cursor to the clicked segment's start vertex,
+// then hands the resulting vertex sequence to `navigate_cursor`.
+fn start_navigation(
+    mut clicks: EventReader<SegmentClicked>,
+    triangle_segments: Query<&TriangleSegment>,
+    cursor_component: Query<&Transform, With<GameCursor>>,
+    path_graph: Res<PathGraph>,
+    mut game: ResMut<Game>,
+) {
+    for SegmentClicked(target_id) in clicks.iter() {
+        let Some(target_segment) = triangle_segments
+            .iter()
+            .find(|segment| segment.id == *target_id)
+        else {
+            continue;
+        };
+
+        let cursor =
+            cursor_component.single().translation.to_vec2();
+
+        let Some((start_vertex, _)) = path_graph
+            .vertices
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                a.distance(cursor).total_cmp(&b.distance(cursor))
+            })
+        else {
+            continue;
+        };
+
+        if let Some(path) =
+            path_graph.astar(start_vertex, target_segment.id)
+        {
+            let waypoints = path
+                .into_iter()
+                .map(|vertex| path_graph.vertices[vertex])
+                .collect();
+            game.cursor_state =
+                CursorState::Navigating(waypoints);
+        }
+    }
+}
+
+// How fast (in pixels per frame) the cursor travels while navigating.
+const NAVIGATION_SPEED: f32 = 6.;
+
+// Drives the cursor along the remaining waypoints of an active
+// `CursorState::Navigating` route, reusing straight-line motion between
+// vertices since consecutive waypoints already lie on the same polygon
+// edge. Falls back to `Unlocked` once the route is exhausted.
+fn navigate_cursor(
+    mut cursor_component: Query<&mut Transform, With<GameCursor>>,
+    mut game: ResMut<Game>,
+) {
+    let CursorState::Navigating(ref mut waypoints) =
+        game.cursor_state
+    else {
+        return;
+    };
+
+    let Some(&target) = waypoints.front() else {
+        game.cursor_state = CursorState::Unlocked;
+        return;
+    };
+
+    let mut transform = cursor_component.single_mut();
+    let cursor = transform.translation.to_vec2();
+    let to_target = target - cursor;
+
+    let next_pos = if to_target.length() <= NAVIGATION_SPEED {
+        waypoints.pop_front();
+        target
+    } else {
+        cursor + to_target.normalize() * NAVIGATION_SPEED
+    };
+
+    transform.translation.x = next_pos.x;
+    transform.translation.y = next_pos.y;
+}
+
+// Spatial index over every `TriangleSegment`'s bounding box, rebuilt
+// whenever the outline's geometry changes (currently just once, at
+// startup). `move_cursor` queries it with the swept cursor-motion AABB
+// instead of scanning every segment, so collision stays cheap as the
+// outline grows past a handful of edges.
+#[derive(Resource)]
+struct SegmentIndex(Quadtree);
+
+fn build_segment_index(
+    mut commands: Commands,
+    triangle_segments: Query<&TriangleSegment>,
+) {
+    let mut bounds: Option<Aabb> = None;
+    for segment in &triangle_segments {
+        let segment_bounds =
+            Aabb::from_points(segment.start, segment.end);
+        match &mut bounds {
+            Some(bounds) => bounds.expand_to_include(&segment_bounds),
+            None => bounds = Some(segment_bounds),
+        }
+    }
+
+    let Some(bounds) = bounds else {
+        return;
+    };
 
-    let xnumerator =
-        det12 * (p3.x - p4.x) - (p1.x - p2.x) * det34;
-    let ynumerator = det12 * yd34 - yd12 * det34;
-    println!("{xnumerator} {ynumerator} {denominator}");
+    let mut tree = Quadtree::new(bounds);
+    for segment in &triangle_segments {
+        tree.insert(
+            segment.id,
+            Aabb::from_points(segment.start, segment.end),
+        );
+    }
 
-    Some(Vec2::new(
-        xnumerator / denominator,
-        ynumerator / denominator,
-    ))
+    commands.insert_resource(SegmentIndex(tree));
 }
@@ -0,0 +1,118 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use bevy::{math::Vec2, prelude::Resource};
+
+// A graph over a polygon's vertices: nodes are vertex indices, edges are
+// the polygon's segments (bidirectional), weighted by their length.
+// Built once from the spawned `TriangleSegment`s and reused by every
+// navigation request.
+#[derive(Resource)]
+pub struct PathGraph {
+    pub vertices: Vec<Vec2>,
+    pub adjacency: HashMap<usize, Vec<(usize, f32)>>,
+}
+
+impl PathGraph {
+    // A* search from `start` to `goal`, using straight-line Euclidean
+    // distance to the goal as the heuristic. It's admissible here because
+    // travelling along the polygon's edges can never be shorter than the
+    // straight line between two vertices.
+    //
+    // Returns the vertex index sequence to walk, including `start` and
+    // `goal`, or `None` if there's no path.
+    pub fn astar(
+        &self,
+        start: usize,
+        goal: usize,
+    ) -> Option<Vec<usize>> {
+        let mut open = BinaryHeap::new();
+        let mut came_from: HashMap<usize, usize> = HashMap::new();
+        let mut g_score: HashMap<usize, f32> = HashMap::new();
+
+        g_score.insert(start, 0.);
+        open.push(OpenEntry {
+            cost: self.heuristic(start, goal),
+            node: start,
+        });
+
+        while let Some(OpenEntry { node, .. }) = open.pop() {
+            if node == goal {
+                return Some(reconstruct_path(&came_from, node));
+            }
+
+            let current_g = g_score[&node];
+
+            let Some(neighbors) = self.adjacency.get(&node) else {
+                continue;
+            };
+
+            for &(neighbor, weight) in neighbors {
+                let tentative_g = current_g + weight;
+                let best_known = g_score
+                    .get(&neighbor)
+                    .copied()
+                    .unwrap_or(f32::INFINITY);
+
+                if tentative_g < best_known {
+                    came_from.insert(neighbor, node);
+                    g_score.insert(neighbor, tentative_g);
+                    open.push(OpenEntry {
+                        cost: tentative_g
+                            + self.heuristic(neighbor, goal),
+                        node: neighbor,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    fn heuristic(&self, from: usize, to: usize) -> f32 {
+        self.vertices[from].distance(self.vertices[to])
+    }
+}
+
+fn reconstruct_path(
+    came_from: &HashMap<usize, usize>,
+    mut node: usize,
+) -> Vec<usize> {
+    let mut path = vec![node];
+    while let Some(&prev) = came_from.get(&node) {
+        path.push(prev);
+        node = prev;
+    }
+    path.reverse();
+    path
+}
+
+// `BinaryHeap` is a max-heap, so entries are ordered by reversed cost to
+// turn it into the min-cost-first open set A* needs.
+struct OpenEntry {
+    cost: f32,
+    node: usize,
+}
+
+impl PartialEq for OpenEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+
+impl Eq for OpenEntry {}
+
+impl Ord for OpenEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .partial_cmp(&self.cost)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for OpenEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
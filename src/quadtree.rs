@@ -0,0 +1,137 @@
+use bevy::math::{vec2, Vec2};
+
+// A node holds up to this many entries before it subdivides its region
+// into four quadrants.
+const MAX_ENTRIES_PER_NODE: usize = 8;
+
+// An axis-aligned bounding box.
+#[derive(Clone, Copy, Debug)]
+pub struct Aabb {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Aabb {
+    pub fn from_points(a: Vec2, b: Vec2) -> Self {
+        Self {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    pub fn expand_to_include(&mut self, other: &Aabb) {
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min.x <= other.max.x
+            && self.max.x >= other.min.x
+            && self.min.y <= other.max.y
+            && self.max.y >= other.min.y
+    }
+
+    pub fn contains(&self, other: &Aabb) -> bool {
+        self.min.x <= other.min.x
+            && self.min.y <= other.min.y
+            && self.max.x >= other.max.x
+            && self.max.y >= other.max.y
+    }
+
+    fn center(&self) -> Vec2 {
+        (self.min + self.max) / 2.
+    }
+}
+
+// A quadtree over `TriangleSegment` bounding boxes, used to cut the
+// per-frame collision scan in `move_cursor` from O(segment count) down
+// to near-logarithmic. Each node holds up to `MAX_ENTRIES_PER_NODE`
+// entries before splitting its region into four quadrants; an entry that
+// doesn't fit entirely inside one quadrant stays at the parent.
+pub struct Quadtree {
+    bounds: Aabb,
+    entries: Vec<(usize, Aabb)>,
+    children: Option<Box<[Quadtree; 4]>>,
+}
+
+impl Quadtree {
+    pub fn new(bounds: Aabb) -> Self {
+        Self {
+            bounds,
+            entries: Vec::new(),
+            children: None,
+        }
+    }
+
+    pub fn insert(&mut self, id: usize, aabb: Aabb) {
+        if let Some(children) = &mut self.children {
+            if let Some(child) = children
+                .iter_mut()
+                .find(|child| child.bounds.contains(&aabb))
+            {
+                child.insert(id, aabb);
+                return;
+            }
+
+            // Straddles more than one quadrant: keep it at this level.
+            self.entries.push((id, aabb));
+            return;
+        }
+
+        self.entries.push((id, aabb));
+
+        if self.entries.len() > MAX_ENTRIES_PER_NODE {
+            self.subdivide();
+        }
+    }
+
+    // Appends the ids of every entry whose bounding box overlaps `aabb`.
+    pub fn query(&self, aabb: &Aabb, out: &mut Vec<usize>) {
+        if !self.bounds.intersects(aabb) {
+            return;
+        }
+
+        out.extend(
+            self.entries
+                .iter()
+                .filter(|(_, entry_aabb)| entry_aabb.intersects(aabb))
+                .map(|(id, _)| *id),
+        );
+
+        if let Some(children) = &self.children {
+            for child in children.iter() {
+                child.query(aabb, out);
+            }
+        }
+    }
+
+    fn subdivide(&mut self) {
+        let center = self.bounds.center();
+
+        let quadrants = [
+            Aabb {
+                min: vec2(self.bounds.min.x, center.y),
+                max: vec2(center.x, self.bounds.max.y),
+            },
+            Aabb {
+                min: center,
+                max: self.bounds.max,
+            },
+            Aabb {
+                min: self.bounds.min,
+                max: center,
+            },
+            Aabb {
+                min: vec2(center.x, self.bounds.min.y),
+                max: vec2(self.bounds.max.x, center.y),
+            },
+        ];
+
+        self.children =
+            Some(Box::new(quadrants.map(Quadtree::new)));
+
+        for (id, aabb) in std::mem::take(&mut self.entries) {
+            self.insert(id, aabb);
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use bevy::math::{vec2, Vec2};
+
+// How far a flattened Bezier segment is allowed to deviate from the
+// true curve before we subdivide it again.
+const FLATNESS_TOLERANCE: f32 = 0.1;
+
+// Parses the subset of SVG path data commands we care about (M, L, C, Q, Z,
+// both absolute and relative) and flattens it into a single closed polyline.
+//
+// Curved commands (C, Q) are flattened with recursive De Casteljau
+// subdivision: split the curve at t=0.5 into two halves and keep splitting
+// until the control points are within `FLATNESS_TOLERANCE` of the chord,
+// then emit the endpoints as line segments.
+pub fn parse_polygon(path: &str) -> Vec<Vec2> {
+    let mut vertices = Vec::new();
+    let mut cursor = Vec2::ZERO;
+    let mut subpath_start = Vec2::ZERO;
+
+    let mut tokens = tokenize(path).into_iter().peekable();
+    let mut command = ' ';
+
+    while let Some(token) = tokens.peek().cloned() {
+        if let Ok(_) = token.parse::<f32>() {
+            // Repeated arguments reuse the previous command letter.
+        } else {
+            command = token.chars().next().unwrap();
+            tokens.next();
+        }
+
+        match command {
+            'M' | 'm' => {
+                let p = read_point(&mut tokens);
+                cursor = if command == 'm' { cursor + p } else { p };
+                subpath_start = cursor;
+                vertices.push(cursor);
+                command = if command == 'm' { 'l' } else { 'L' };
+            }
+            'L' | 'l' => {
+                let p = read_point(&mut tokens);
+                cursor = if command == 'l' { cursor + p } else { p };
+                vertices.push(cursor);
+            }
+            'C' | 'c' => {
+                let c1 = read_point(&mut tokens);
+                let c2 = read_point(&mut tokens);
+                let end = read_point(&mut tokens);
+                let (c1, c2, end) = if command == 'c' {
+                    (cursor + c1, cursor + c2, cursor + end)
+                } else {
+                    (c1, c2, end)
+                };
+                flatten_cubic(cursor, c1, c2, end, &mut vertices);
+                cursor = end;
+            }
+            'Q' | 'q' => {
+                let c1 = read_point(&mut tokens);
+                let end = read_point(&mut tokens);
+                let (c1, end) = if command == 'q' {
+                    (cursor + c1, cursor + end)
+                } else {
+                    (c1, end)
+                };
+                flatten_quadratic(cursor, c1, end, &mut vertices);
+                cursor = end;
+            }
+            'Z' | 'z' => {
+                cursor = subpath_start;
+            }
+            _ => {
+                // Unsupported command: skip the token so we don't loop forever.
+                tokens.next();
+            }
+        }
+    }
+
+    // A closed path's last vertex often duplicates the first; drop it so
+    // callers get a clean vertex loop to build edges from.
+    if vertices.len() > 1
+        && vertices.first() == vertices.last()
+    {
+        vertices.pop();
+    }
+
+    vertices
+}
+
+fn tokenize(path: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+
+    for c in path.chars() {
+        if c.is_ascii_alphabetic() {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            tokens.push(c.to_string());
+        } else if c == ',' || c.is_whitespace() {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+        } else if c == '-' && !current.is_empty()
+            && !current.ends_with('e')
+            && !current.ends_with('E')
+        {
+            tokens.push(std::mem::take(&mut current));
+            current.push(c);
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+fn read_point(
+    tokens: &mut std::iter::Peekable<std::vec::IntoIter<String>>,
+) -> Vec2 {
+    let x = tokens
+        .next()
+        .and_then(|t| t.parse::<f32>().ok())
+        .unwrap_or(0.);
+    let y = tokens
+        .next()
+        .and_then(|t| t.parse::<f32>().ok())
+        .unwrap_or(0.);
+    vec2(x, y)
+}
+
+fn flatten_cubic(
+    p0: Vec2,
+    p1: Vec2,
+    p2: Vec2,
+    p3: Vec2,
+    out: &mut Vec<Vec2>,
+) {
+    if cubic_is_flat(p0, p1, p2, p3) {
+        out.push(p3);
+        return;
+    }
+
+    // De Casteljau split at t=0.5
+    let p01 = (p0 + p1) / 2.;
+    let p12 = (p1 + p2) / 2.;
+    let p23 = (p2 + p3) / 2.;
+    let p012 = (p01 + p12) / 2.;
+    let p123 = (p12 + p23) / 2.;
+    let mid = (p012 + p123) / 2.;
+
+    flatten_cubic(p0, p01, p012, mid, out);
+    flatten_cubic(mid, p123, p23, p3, out);
+}
+
+fn cubic_is_flat(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2) -> bool {
+    point_to_chord_distance(p1, p0, p3) < FLATNESS_TOLERANCE
+        && point_to_chord_distance(p2, p0, p3) < FLATNESS_TOLERANCE
+}
+
+fn flatten_quadratic(p0: Vec2, p1: Vec2, p2: Vec2, out: &mut Vec<Vec2>) {
+    if point_to_chord_distance(p1, p0, p2) < FLATNESS_TOLERANCE {
+        out.push(p2);
+        return;
+    }
+
+    // De Casteljau split at t=0.5
+    let p01 = (p0 + p1) / 2.;
+    let p12 = (p1 + p2) / 2.;
+    let mid = (p01 + p12) / 2.;
+
+    flatten_quadratic(p0, p01, mid, out);
+    flatten_quadratic(mid, p12, p2, out);
+}
+
+fn point_to_chord_distance(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let chord = b - a;
+    if chord.length_squared() == 0. {
+        return p.distance(a);
+    }
+    let t = ((p - a).dot(chord) / chord.length_squared()).clamp(0., 1.);
+    let projected = a + chord * t;
+    p.distance(projected)
+}